@@ -1,14 +1,23 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
+use core::mem;
 use core::mem::MaybeUninit;
 use tiny_serde::Deserialize;
 #[cfg(feature = "defmt")]
 use defmt::Format;
+#[cfg(feature = "heapless")]
+use heapless::Vec as HeaplessVec;
 
+#[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(Format))]
 pub enum PatternError {
     NotFound, // end of iter was reached when looking for value
     FailedDeserialize(usize), // type could not be deserialized from data
+    Mismatch { index: usize }, // an expected literal value did not match at this index
+    TrailingBytes, // items remained in the iterator after the pattern was expected to finish
+    #[cfg(feature = "heapless")]
+    CapacityExceeded, // a length-prefixed sequence reported more elements than the destination could hold
+    InvalidTag, // a discriminant did not match any known variant
 }
 
 /// Expects N values of any value immediately.
@@ -97,6 +106,58 @@ where
     }
 }
 
+/// Scans for items and discards them without materializing a value.
+pub struct IgnoreStrategy<'a, I>
+where
+    I: Iterator
+{
+    pattern: &'a mut Pattern<I>
+}
+
+impl<'a, I> IgnoreStrategy<'a, I>
+where
+    I: Iterator
+{
+    fn new(pattern: &'a mut Pattern<I>) -> Self {
+        Self { pattern }
+    }
+
+    /// Advances past `count` items without materializing them.
+    pub fn skip(&mut self, count: usize) -> Result<(), PatternError> {
+        self.pattern.collect(count, |_, _| Ok(()))
+    }
+}
+
+/// Expects a literal sequence of values, such as a magic number or sync word.
+pub struct ExpectStrategy<'a, I>
+where
+    I: Iterator,
+    I::Item: PartialEq
+{
+    pattern: &'a mut Pattern<I>
+}
+
+impl<'a, I> ExpectStrategy<'a, I>
+where
+    I: Iterator,
+    I::Item: PartialEq
+{
+    fn new(pattern: &'a mut Pattern<I>) -> Self {
+        Self { pattern }
+    }
+
+    /// Consumes `expected.len()` items, verifying each one matches in turn.
+    pub fn matches(&mut self, expected: &[I::Item]) -> Result<(), PatternError> {
+        self.pattern.collect(expected.len(), |i, candidate| {
+            if candidate == expected[i] {
+                Ok(())
+            } else {
+                Err(PatternError::Mismatch { index: i })
+            }
+        })
+    }
+}
+
 /// Facilitates the extraction and validation of desired sequences of items from an iterator.
 #[derive(Clone)]
 pub struct Pattern<I>
@@ -124,7 +185,7 @@ where
         for i in 0..count {
             if let Some(candidate) = self.iter.next() {
                 if let Err(e) = callback(i, candidate) {
-                    self.count += i;
+                    self.count += i + 1;
                     return Err(e);
                 }
             } else {
@@ -143,6 +204,15 @@ where
         AnyStrategy::new(self)
     }
 
+    /// Deserializes a single `T` from the next `K` bytes.
+    fn get_one<T, const K: usize>(&mut self) -> Result<T, PatternError>
+    where
+        I: Iterator<Item = u8>,
+        T: Deserialize<K> + Copy,
+    {
+        Ok(self.get::<1>().extract::<T, K>()?[0])
+    }
+
     /// Dispatches a [GetStrategy].
     pub fn get<'a, const N: usize>(&mut self) -> GetStrategy<I, N>
     where
@@ -151,7 +221,266 @@ where
         GetStrategy::new(self)
     }
 
+    /// Dispatches an [IgnoreStrategy].
+    fn ignore(&mut self) -> IgnoreStrategy<I> {
+        IgnoreStrategy::new(self)
+    }
+
+    /// Dispatches an [ExpectStrategy].
+    fn expect_seq(&mut self) -> ExpectStrategy<I>
+    where
+        I::Item: PartialEq
+    {
+        ExpectStrategy::new(self)
+    }
+
+    /// Consumes and verifies a literal sequence of values, such as a magic
+    /// number or sync word, failing with [PatternError::Mismatch] on the
+    /// first divergence.
+    #[inline]
+    pub fn expect(&mut self, expected: &[I::Item]) -> Result<(), PatternError>
+    where
+        I::Item: PartialEq
+    {
+        self.expect_seq().matches(expected)
+    }
+
+    /// Advances past `count` items without materializing them, for when a caller
+    /// only needs to skip padding, reserved fields, or a known-length header.
+    #[inline]
+    pub fn skip(&mut self, count: usize) -> Result<(), PatternError> {
+        self.ignore().skip(count)
+    }
+
+    /// Deserializes a length prefix of type `L`, then that many `T` values into a
+    /// fixed-capacity [`heapless::Vec`], for variable-length arrays whose element
+    /// count is data-driven rather than known at compile time.
+    #[cfg(feature = "heapless")]
+    pub fn get_prefixed<L, const LK: usize, T, const TK: usize, const CAP: usize>(&mut self) -> Result<HeaplessVec<T, CAP>, PatternError>
+    where
+        I: Iterator<Item = u8>,
+        L: Deserialize<LK> + Copy,
+        usize: From<L>,
+        T: Deserialize<TK> + Copy,
+    {
+        let len: usize = self.get_one::<L, LK>()?.into();
+
+        if len > CAP {
+            return Err(PatternError::CapacityExceeded);
+        }
+
+        let mut result = HeaplessVec::new();
+
+        for _ in 0..len {
+            let value = self.get_one::<T, TK>()?;
+            let _ = result.push(value);
+        }
+
+        Ok(result)
+    }
+
+    /// Deserializes only the `index`-th element of a fixed-layout record array,
+    /// skipping the preceding elements without materializing them.
+    pub fn nth<T, const K: usize>(&mut self, index: usize) -> Result<T, PatternError>
+    where
+        I: Iterator<Item = u8>,
+        T: Deserialize<K> + Copy,
+    {
+        self.skip(index * K)?;
+
+        self.get_one::<T, K>()
+    }
+
+    /// Deserializes a discriminant of type `D`, then invokes `dispatch` to parse
+    /// the variant-specific body at the same stream position. `dispatch` should
+    /// return [PatternError::InvalidTag] if the discriminant is unrecognized.
+    pub fn branch<D, const K: usize, T, F>(&mut self, mut dispatch: F) -> Result<T, PatternError>
+    where
+        I: Iterator<Item = u8>,
+        D: Deserialize<K> + Copy,
+        F: FnMut(D, &mut Pattern<I>) -> Result<T, PatternError>,
+    {
+        let tag = self.get_one::<D, K>()?;
+
+        dispatch(tag, self)
+    }
+
     pub fn count(&self) -> usize {
         self.count
     }
+
+    /// Asserts the iterator is exhausted, returning the total number of items
+    /// consumed, or [PatternError::TrailingBytes] if items remain.
+    pub fn finish(mut self) -> Result<usize, PatternError> {
+        if self.iter.next().is_none() {
+            Ok(self.count)
+        } else {
+            Err(PatternError::TrailingBytes)
+        }
+    }
+
+    /// Tries each branch in order against a clone of the current state, committing
+    /// the first one that succeeds and leaving `self` completely unmodified if all
+    /// branches fail.
+    pub fn alt<T>(&mut self, branches: &mut [&mut dyn FnMut(&mut Pattern<I>) -> Result<T, PatternError>]) -> Result<T, PatternError>
+    where
+        I: Clone,
+    {
+        let mut last_err = PatternError::NotFound;
+
+        for branch in branches.iter_mut() {
+            let mut attempt = self.clone();
+
+            match branch(&mut attempt) {
+                Ok(value) => {
+                    mem::swap(self, &mut attempt);
+                    return Ok(value);
+                }
+                Err(e) => last_err = e,
+            }
+        }
+
+        Err(last_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expect_mismatch_counts_the_compared_item_itself() {
+        let mut pattern = Pattern::new([0xAA, 0xBB, 0x00].iter().copied());
+
+        let err = pattern.expect(&[0xAA, 0xBB, 0xFF]).unwrap_err();
+
+        assert!(matches!(err, PatternError::Mismatch { index: 2 }));
+        assert_eq!(pattern.count(), 3);
+    }
+
+    #[test]
+    fn alt_leaves_pattern_unmodified_after_a_failed_branch() {
+        let mut pattern = Pattern::new([0xAA, 0xBB, 0xCC].iter().copied());
+
+        let mut wrong = |p: &mut Pattern<_>| p.expect(&[0xFF]);
+        let mut right = |p: &mut Pattern<_>| p.expect(&[0xAA]);
+
+        pattern.alt(&mut [&mut wrong, &mut right]).unwrap();
+
+        // Only the winning branch's single byte should be reflected, not the
+        // failed attempt's advance (one byte, since the mismatched byte is consumed).
+        assert_eq!(pattern.count(), 1);
+        pattern.expect(&[0xBB]).unwrap();
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn get_prefixed_reads_len_then_that_many_elements() {
+        let mut pattern = Pattern::new([3u8, 0x10, 0x20, 0x30, 0x99].iter().copied());
+
+        let values = pattern.get_prefixed::<u8, 1, u8, 1, 4>().unwrap();
+
+        assert_eq!(values.as_slice(), &[0x10, 0x20, 0x30]);
+        pattern.expect(&[0x99]).unwrap();
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn get_prefixed_rejects_len_greater_than_capacity() {
+        let mut pattern = Pattern::new([5u8].iter().copied());
+
+        let err = pattern.get_prefixed::<u8, 1, u8, 1, 4>().unwrap_err();
+
+        assert!(matches!(err, PatternError::CapacityExceeded));
+    }
+
+    #[test]
+    fn skip_advances_past_n_items_without_materializing_them() {
+        let mut pattern = Pattern::new([0x01, 0x02, 0x03, 0x04].iter().copied());
+
+        pattern.skip(2).unwrap();
+
+        assert_eq!(pattern.count(), 2);
+        pattern.expect(&[0x03]).unwrap();
+    }
+
+    #[test]
+    fn skip_past_the_end_returns_not_found() {
+        let mut pattern = Pattern::new([0x01, 0x02].iter().copied());
+
+        let err = pattern.skip(5).unwrap_err();
+
+        assert!(matches!(err, PatternError::NotFound));
+    }
+
+    #[test]
+    fn finish_on_exhausted_iterator_returns_the_final_count() {
+        let mut pattern = Pattern::new([0xAA, 0xBB].iter().copied());
+
+        pattern.expect(&[0xAA, 0xBB]).unwrap();
+
+        assert_eq!(pattern.finish().unwrap(), 2);
+    }
+
+    #[test]
+    fn finish_with_items_remaining_returns_trailing_bytes() {
+        let mut pattern = Pattern::new([0xAA, 0xBB, 0xCC].iter().copied());
+
+        pattern.expect(&[0xAA]).unwrap();
+
+        assert!(matches!(pattern.finish(), Err(PatternError::TrailingBytes)));
+    }
+
+    #[test]
+    fn nth_skips_preceding_elements_and_counts_the_skipped_bytes() {
+        // Each u16 is encoded with both of its bytes equal, so the
+        // decoded value is unambiguous regardless of the endianness tiny_serde uses.
+        let mut pattern = Pattern::new([0x10, 0x10, 0x20, 0x20, 0x30, 0x30].iter().copied());
+
+        let value = pattern.nth::<u16, 2>(1).unwrap();
+
+        assert_eq!(value, 0x2020);
+        assert_eq!(pattern.count(), 4);
+    }
+
+    #[test]
+    fn nth_past_the_end_returns_not_found() {
+        let mut pattern = Pattern::new([0x10, 0x10, 0x20, 0x20].iter().copied());
+
+        let err = pattern.nth::<u16, 2>(5).unwrap_err();
+
+        assert!(matches!(err, PatternError::NotFound));
+    }
+
+    #[test]
+    fn branch_reads_tag_then_dispatches_body_at_the_same_position() {
+        let mut pattern = Pattern::new([0x01, 0xAA, 0xBB].iter().copied());
+
+        let value = pattern
+            .branch::<u8, 1, u8, _>(|tag, p| match tag {
+                0x01 => {
+                    p.expect(&[0xAA, 0xBB])?;
+                    Ok(tag)
+                }
+                _ => Err(PatternError::InvalidTag),
+            })
+            .unwrap();
+
+        assert_eq!(value, 0x01);
+        assert_eq!(pattern.count(), 3);
+    }
+
+    #[test]
+    fn branch_propagates_invalid_tag_from_dispatch() {
+        let mut pattern = Pattern::new([0xFF, 0xAA, 0xBB].iter().copied());
+
+        let err = pattern
+            .branch::<u8, 1, u8, _>(|tag, _| match tag {
+                0x01 => Ok(tag),
+                _ => Err(PatternError::InvalidTag),
+            })
+            .unwrap_err();
+
+        assert!(matches!(err, PatternError::InvalidTag));
+    }
 }
\ No newline at end of file